@@ -0,0 +1,78 @@
+use num::{BigUint, Zero};
+use std::collections::BTreeMap;
+
+/// Identifier of an account in the Plasma state tree.
+pub type AccountId = u32;
+
+/// Identifier of a token recognized by the network.
+pub type TokenId = u16;
+
+/// Account nonce, incremented on every state-changing transaction signed by the account owner.
+pub type Nonce = u32;
+
+/// A balance is never negative; `NonNegativeBalance::zero()` is the canonical way to
+/// represent "no balance" without callers special-casing `BigUint::from(0u32)`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NonNegativeBalance(pub BigUint);
+
+impl NonNegativeBalance {
+    pub fn zero() -> Self {
+        Self(BigUint::zero())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+/// In-memory representation of an account's balances and nonce, as tracked by `PlasmaState`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    pub id: AccountId,
+    pub nonce: Nonce,
+    balances: BTreeMap<TokenId, BigUint>,
+}
+
+impl Account {
+    pub fn default_with_id(id: AccountId) -> Self {
+        Self {
+            id,
+            nonce: 0,
+            balances: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_balance(&self, token: TokenId) -> BigUint {
+        self.balances.get(&token).cloned().unwrap_or_default()
+    }
+
+    pub fn set_balance(&mut self, token: TokenId, amount: BigUint) {
+        self.balances.insert(token, amount);
+    }
+
+    /// Returns every `(token, balance)` pair with a non-zero balance, in ascending token order.
+    pub fn nonzero_balances(&self) -> BTreeMap<TokenId, BigUint> {
+        self.balances
+            .iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(token, amount)| (*token, amount.clone()))
+            .collect()
+    }
+}
+
+/// A single state transition applied to an account, as recorded in the block witness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountUpdate {
+    Create {
+        nonce: Nonce,
+    },
+    Delete {
+        nonce: Nonce,
+    },
+    UpdateBalance {
+        old_nonce: Nonce,
+        new_nonce: Nonce,
+        /// `(token, old_amount, new_amount)`
+        balance_update: (TokenId, BigUint, BigUint),
+    },
+}
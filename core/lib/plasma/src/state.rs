@@ -0,0 +1,304 @@
+use models::account::{Account, AccountId, AccountUpdate, TokenId};
+use num::{BigUint, Zero};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A fee collected from a block's transactions for a single token, pending distribution
+/// to whichever account(s) should be credited with it.
+#[derive(Debug, Clone)]
+pub struct CollectedFee {
+    pub token: TokenId,
+    pub amount: BigUint,
+}
+
+/// Reasons `try_collect_fee` can fail without panicking, so the block-building path can
+/// reject a batch gracefully instead of aborting the process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeError {
+    /// The fee account is not present in the state.
+    UnknownAccount(AccountId),
+    /// Crediting the fee would push `account`'s balance of `token` past its configured ceiling.
+    BalanceOverflow { account: AccountId, token: TokenId },
+}
+
+impl fmt::Display for FeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeError::UnknownAccount(account_id) => {
+                write!(f, "account {} does not exist", account_id)
+            }
+            FeeError::BalanceOverflow { account, token } => write!(
+                f,
+                "crediting account {} would exceed the balance ceiling for token {}",
+                account, token
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FeeError {}
+
+/// In-memory Plasma account state, keyed by account id.
+///
+/// This is the state the block-building pipeline mutates as it applies transactions;
+/// every mutation is reported back as a list of `AccountUpdate`s so the witness generator
+/// and the state keeper's event log can replay it.
+#[derive(Debug, Clone, Default)]
+pub struct PlasmaState {
+    accounts: BTreeMap<AccountId, Account>,
+    /// Optional per-token balance ceiling enforced by `try_collect_fee`. Tokens absent
+    /// from this map are uncapped.
+    balance_ceilings: BTreeMap<TokenId, BigUint>,
+    /// Running total per token, kept in sync with every `UpdateBalance` this state
+    /// produces. Balances written directly through `insert_account` are not counted.
+    /// See `total_supply` and `assert_capitalization_consistent`.
+    capitalization: BTreeMap<TokenId, BigUint>,
+}
+
+impl PlasmaState {
+    pub fn empty() -> Self {
+        Self {
+            accounts: BTreeMap::new(),
+            balance_ceilings: BTreeMap::new(),
+            capitalization: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_account(&self, account_id: AccountId) -> Option<Account> {
+        self.accounts.get(&account_id).cloned()
+    }
+
+    pub fn insert_account(&mut self, account_id: AccountId, account: Account) {
+        self.accounts.insert(account_id, account);
+    }
+
+    /// Every known account id, in ascending order.
+    pub fn get_account_ids(&self) -> Vec<AccountId> {
+        self.accounts.keys().copied().collect()
+    }
+
+    /// `account_id`'s per-token balances, omitting any token with a zero balance.
+    pub fn account_balances(&self, account_id: AccountId) -> BTreeMap<TokenId, BigUint> {
+        self.get_account(account_id)
+            .map(|account| account.nonzero_balances())
+            .unwrap_or_default()
+    }
+
+    /// Every known account's non-zero per-token balances, e.g. for fee-reporting or
+    /// reconciliation tooling to verify where a round of `collect_fee`/`distribute_fees`
+    /// calls landed.
+    pub fn balances_summary(&self) -> BTreeMap<AccountId, BTreeMap<TokenId, BigUint>> {
+        self.get_account_ids()
+            .into_iter()
+            .map(|account_id| (account_id, self.account_balances(account_id)))
+            .collect()
+    }
+
+    /// The running total of `token` credited through `collect_fee`, `try_collect_fee` and
+    /// `distribute_fees`. Balances written directly through `insert_account` are not counted.
+    pub fn total_supply(&self, token: TokenId) -> BigUint {
+        self.capitalization.get(&token).cloned().unwrap_or_default()
+    }
+
+    /// Folds a balance change of `token` from `old_amount` to `new_amount` into the
+    /// running `capitalization` total for that token.
+    fn update_capitalization(&mut self, token: TokenId, old_amount: &BigUint, new_amount: &BigUint) {
+        let total = self.capitalization.entry(token).or_insert_with(BigUint::zero);
+        if new_amount >= old_amount {
+            *total += new_amount - old_amount;
+        } else {
+            *total -= old_amount - new_amount;
+        }
+    }
+
+    /// Re-sums every account's balance per token and asserts it matches the
+    /// incrementally maintained `capitalization` total, i.e. that the state hasn't
+    /// drifted from the sum of its accounts. Debug-only: this is an O(accounts) sanity
+    /// check, not something the hot path should pay for in release builds.
+    #[cfg(debug_assertions)]
+    pub fn assert_capitalization_consistent(&self) {
+        let mut actual: BTreeMap<TokenId, BigUint> = BTreeMap::new();
+        for account in self.accounts.values() {
+            for (token, balance) in account.nonzero_balances() {
+                *actual.entry(token).or_insert_with(BigUint::zero) += balance;
+            }
+        }
+
+        for (token, tracked_total) in &self.capitalization {
+            let actual_total = actual.get(token).cloned().unwrap_or_default();
+            assert_eq!(
+                &actual_total, tracked_total,
+                "capitalization for token {} drifted: accounts sum to {} but {} is tracked",
+                token, actual_total, tracked_total
+            );
+        }
+    }
+
+    /// Sets the maximum balance `token` may reach via `try_collect_fee`, or removes the
+    /// cap entirely when `ceiling` is `None`.
+    pub fn set_balance_ceiling(&mut self, token: TokenId, ceiling: Option<BigUint>) {
+        match ceiling {
+            Some(ceiling) => {
+                self.balance_ceilings.insert(token, ceiling);
+            }
+            None => {
+                self.balance_ceilings.remove(&token);
+            }
+        }
+    }
+
+    /// Credits every collected fee to `fee_account`, returning one `UpdateBalance` per
+    /// token whose amount was non-zero.
+    ///
+    /// Panics if `fee_account` is not a known account or a credit would exceed a
+    /// configured balance ceiling; see `try_collect_fee` for a non-panicking equivalent.
+    pub fn collect_fee(
+        &mut self,
+        fees: &[CollectedFee],
+        fee_account: AccountId,
+    ) -> Vec<(AccountId, AccountUpdate)> {
+        self.try_collect_fee(fees, fee_account).unwrap()
+    }
+
+    /// Like `collect_fee`, but returns a `FeeError` instead of panicking when `fee_account`
+    /// is unknown or a credit would exceed a configured balance ceiling, leaving the state
+    /// untouched in either case.
+    pub fn try_collect_fee(
+        &mut self,
+        fees: &[CollectedFee],
+        fee_account: AccountId,
+    ) -> Result<Vec<(AccountId, AccountUpdate)>, FeeError> {
+        let mut account = self
+            .get_account(fee_account)
+            .ok_or(FeeError::UnknownAccount(fee_account))?;
+
+        // Staged (token, old_amount, new_amount) deltas, folded into `self.capitalization`
+        // only once the whole batch has succeeded, so a later fee overflowing leaves
+        // `self` byte-for-byte unchanged rather than partially applied.
+        let mut capitalization_deltas = Vec::new();
+        let mut updates = Vec::new();
+        for fee in fees {
+            if fee.amount == BigUint::zero() {
+                continue;
+            }
+
+            let old_nonce = account.nonce;
+            let old_amount = account.get_balance(fee.token);
+            let new_amount = &old_amount + &fee.amount;
+
+            let exceeds_ceiling = self
+                .balance_ceilings
+                .get(&fee.token)
+                .is_some_and(|ceiling| &new_amount > ceiling);
+            if exceeds_ceiling {
+                return Err(FeeError::BalanceOverflow {
+                    account: fee_account,
+                    token: fee.token,
+                });
+            }
+
+            account.set_balance(fee.token, new_amount.clone());
+            capitalization_deltas.push((fee.token, old_amount.clone(), new_amount.clone()));
+
+            updates.push((
+                fee_account,
+                AccountUpdate::UpdateBalance {
+                    old_nonce,
+                    new_nonce: account.nonce,
+                    balance_update: (fee.token, old_amount, new_amount),
+                },
+            ));
+        }
+
+        for (token, old_amount, new_amount) in capitalization_deltas {
+            self.update_capitalization(token, &old_amount, &new_amount);
+        }
+        self.insert_account(fee_account, account);
+        Ok(updates)
+    }
+
+    /// Splits each collected fee across `recipients` proportionally to their weight,
+    /// crediting `share_i = amount * weight_i / total_weight` (floor division) to
+    /// recipient `i`, and routing the floor-division dust to the highest-weight
+    /// recipient so that `sum(shares) == amount` always holds.
+    ///
+    /// Recipients with a zero resulting share are skipped, the same way `collect_fee`
+    /// skips zero-amount fees. Panics if any recipient account does not exist, or if
+    /// `recipients` is empty or all weights are zero.
+    pub fn distribute_fees(
+        &mut self,
+        fees: &[CollectedFee],
+        recipients: &[(AccountId, u32)],
+    ) -> Vec<(AccountId, AccountUpdate)> {
+        assert!(!recipients.is_empty(), "must have at least one recipient");
+        let total_weight: u32 = recipients.iter().map(|(_, weight)| weight).sum();
+        assert!(total_weight > 0, "total recipient weight must be non-zero");
+        for (recipient, _) in recipients {
+            assert!(
+                self.accounts.contains_key(recipient),
+                "fee recipient account does not exist"
+            );
+        }
+
+        let (dust_recipient, _) = recipients
+            .iter()
+            .max_by_key(|(_, weight)| *weight)
+            .copied()
+            .expect("recipients is non-empty");
+
+        let mut updates = Vec::new();
+        for fee in fees {
+            if fee.amount == BigUint::zero() {
+                continue;
+            }
+
+            let total_weight = BigUint::from(total_weight);
+            let mut distributed = BigUint::zero();
+            let mut shares = Vec::with_capacity(recipients.len());
+
+            for &(recipient, weight) in recipients {
+                let share = &fee.amount * BigUint::from(weight) / &total_weight;
+                distributed += &share;
+                shares.push((recipient, share));
+            }
+
+            // Assign the dust left over from flooring each share to the highest-weight
+            // recipient, so the shares still sum to the full collected amount.
+            let dust = &fee.amount - &distributed;
+            for (recipient, share) in &mut shares {
+                if *recipient == dust_recipient {
+                    *share += &dust;
+                    break;
+                }
+            }
+
+            for (recipient, share) in shares {
+                if share == BigUint::zero() {
+                    continue;
+                }
+
+                let mut account = self
+                    .get_account(recipient)
+                    .expect("fee recipient account does not exist");
+
+                let old_nonce = account.nonce;
+                let old_amount = account.get_balance(fee.token);
+                let new_amount = &old_amount + &share;
+                account.set_balance(fee.token, new_amount.clone());
+                self.update_capitalization(fee.token, &old_amount, &new_amount);
+                self.insert_account(recipient, account.clone());
+
+                updates.push((
+                    recipient,
+                    AccountUpdate::UpdateBalance {
+                        old_nonce,
+                        new_nonce: account.nonce,
+                        balance_update: (fee.token, old_amount, new_amount),
+                    },
+                ));
+            }
+        }
+
+        updates
+    }
+}
@@ -0,0 +1,41 @@
+use super::PlasmaTestBuilder;
+use models::account::NonNegativeBalance;
+use num::BigUint;
+use std::collections::BTreeMap;
+
+#[test]
+fn get_account_ids_is_sorted() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (first, _, _) = tb.add_account(false);
+    let (second, _, _) = tb.add_account(false);
+
+    assert_eq!(tb.state.get_account_ids(), vec![first, second]);
+}
+
+#[test]
+fn account_balances_omits_zero_tokens() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (account_id, _, _) = tb.add_account(false);
+    tb.set_balance(account_id, 0, 10u32);
+    tb.set_balance(account_id, 1, NonNegativeBalance::zero().0);
+
+    let mut expected = BTreeMap::new();
+    expected.insert(0, BigUint::from(10u32));
+    assert_eq!(tb.state.account_balances(account_id), expected);
+}
+
+#[test]
+fn balances_summary_covers_every_account() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (a, _, _) = tb.add_account(false);
+    let (b, _, _) = tb.add_account(false);
+    tb.set_balance(a, 0, 7u32);
+
+    let mut expected = BTreeMap::new();
+    let mut a_balances = BTreeMap::new();
+    a_balances.insert(0, BigUint::from(7u32));
+    expected.insert(a, a_balances);
+    expected.insert(b, BTreeMap::new());
+
+    assert_eq!(tb.state.balances_summary(), expected);
+}
@@ -0,0 +1,45 @@
+use super::PlasmaTestBuilder;
+use crate::state::CollectedFee;
+use num::BigUint;
+
+#[test]
+fn collect_fee_tracks_total_supply() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (account_id, _, _) = tb.add_account(false);
+
+    tb.state.collect_fee(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(5u32),
+        }],
+        account_id,
+    );
+    tb.state.collect_fee(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(10u32),
+        }],
+        account_id,
+    );
+
+    assert_eq!(tb.state.total_supply(0), BigUint::from(15u32));
+    tb.state.assert_capitalization_consistent();
+}
+
+#[test]
+fn distribute_fees_tracks_total_supply_across_recipients() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (a, _, _) = tb.add_account(false);
+    let (b, _, _) = tb.add_account(false);
+
+    tb.state.distribute_fees(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(10u32),
+        }],
+        &[(a, 1), (b, 1)],
+    );
+
+    assert_eq!(tb.state.total_supply(0), BigUint::from(10u32));
+    tb.state.assert_capitalization_consistent();
+}
@@ -1,5 +1,5 @@
 use super::PlasmaTestBuilder;
-use crate::state::CollectedFee;
+use crate::state::{CollectedFee, FeeError};
 use models::account::AccountUpdate;
 use num::{BigUint, Zero};
 
@@ -58,3 +58,125 @@ fn invalid_account() {
     let mut tb = PlasmaTestBuilder::new();
     tb.state.collect_fee(&[], 145);
 }
+
+#[test]
+fn try_collect_fee_unknown_account() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let result = tb.state.try_collect_fee(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(1u32),
+        }],
+        145,
+    );
+
+    assert_eq!(result, Err(FeeError::UnknownAccount(145)));
+}
+
+#[test]
+fn try_collect_fee_balance_overflow_leaves_state_untouched() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (account_id, _, _) = tb.add_account(false);
+    tb.set_balance(account_id, 0, 90u32);
+    tb.state.set_balance_ceiling(0, Some(BigUint::from(100u32)));
+
+    let state_before = tb.state.clone();
+
+    let result = tb.state.try_collect_fee(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(20u32),
+        }],
+        account_id,
+    );
+
+    assert_eq!(
+        result,
+        Err(FeeError::BalanceOverflow {
+            account: account_id,
+            token: 0,
+        })
+    );
+    assert_eq!(tb.state.get_account(account_id), state_before.get_account(account_id));
+}
+
+#[test]
+fn try_collect_fee_balance_overflow_mid_batch_leaves_state_untouched() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (account_id, _, _) = tb.add_account(false);
+    tb.state.set_balance_ceiling(1, Some(BigUint::from(10u32)));
+
+    tb.state.collect_fee(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(50u32),
+        }],
+        account_id,
+    );
+    let state_before = tb.state.clone();
+
+    // The first fee (token 0) is within its (unset) ceiling and would be applied before
+    // the loop reaches the second fee (token 1), which overflows its ceiling.
+    let result = tb.state.try_collect_fee(
+        &[
+            CollectedFee {
+                token: 0,
+                amount: BigUint::from(50u32),
+            },
+            CollectedFee {
+                token: 1,
+                amount: BigUint::from(20u32),
+            },
+        ],
+        account_id,
+    );
+
+    assert_eq!(
+        result,
+        Err(FeeError::BalanceOverflow {
+            account: account_id,
+            token: 1,
+        })
+    );
+    assert_eq!(
+        tb.state.account_balances(account_id),
+        state_before.account_balances(account_id)
+    );
+    assert_eq!(tb.state.total_supply(0), state_before.total_supply(0));
+    assert_eq!(tb.state.total_supply(1), state_before.total_supply(1));
+    tb.state.assert_capitalization_consistent();
+}
+
+#[test]
+fn try_collect_fee_within_ceiling_succeeds() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (account_id, account, _) = tb.add_account(false);
+    tb.set_balance(account_id, 0, 90u32);
+    tb.state.set_balance_ceiling(0, Some(BigUint::from(100u32)));
+
+    let nonce = account.nonce;
+    let mut state_clone = tb.state.clone();
+
+    let actual_updates = tb
+        .state
+        .try_collect_fee(
+            &[CollectedFee {
+                token: 0,
+                amount: BigUint::from(10u32),
+            }],
+            account_id,
+        )
+        .expect("fee is within the configured ceiling");
+
+    let expected_updates = [(
+        account_id,
+        AccountUpdate::UpdateBalance {
+            old_nonce: nonce,
+            new_nonce: nonce,
+            balance_update: (0, BigUint::from(90u32), BigUint::from(100u32)),
+        },
+    )];
+
+    tb.compare_updates(&expected_updates, &actual_updates, &mut state_clone)
+}
@@ -0,0 +1,102 @@
+use super::PlasmaTestBuilder;
+use crate::state::CollectedFee;
+use models::account::AccountUpdate;
+use num::BigUint;
+
+#[test]
+fn success() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (validator_a, validator_a_account, _) = tb.add_account(false);
+    let (validator_b, validator_b_account, _) = tb.add_account(false);
+    let (treasury, treasury_account, _) = tb.add_account(false);
+
+    let mut state_clone = tb.state.clone();
+
+    let actual_updates = tb.state.distribute_fees(
+        &[
+            CollectedFee {
+                token: 0,
+                amount: BigUint::from(100u32),
+            },
+            CollectedFee {
+                token: 1,
+                amount: BigUint::from(0u32),
+            },
+        ],
+        &[(validator_a, 3), (validator_b, 3), (treasury, 1)],
+    );
+
+    // 100 split 3:3:1 over a total weight of 7 floors to 42/42/14 with 2 left over as
+    // dust, which goes to the last recipient tied for the highest weight (validator_b).
+    let expected_updates = [
+        (
+            validator_a,
+            AccountUpdate::UpdateBalance {
+                old_nonce: validator_a_account.nonce,
+                new_nonce: validator_a_account.nonce,
+                balance_update: (0, BigUint::from(0u32), BigUint::from(42u32)),
+            },
+        ),
+        (
+            validator_b,
+            AccountUpdate::UpdateBalance {
+                old_nonce: validator_b_account.nonce,
+                new_nonce: validator_b_account.nonce,
+                balance_update: (0, BigUint::from(0u32), BigUint::from(44u32)),
+            },
+        ),
+        (
+            treasury,
+            AccountUpdate::UpdateBalance {
+                old_nonce: treasury_account.nonce,
+                new_nonce: treasury_account.nonce,
+                balance_update: (0, BigUint::from(0u32), BigUint::from(14u32)),
+            },
+        ),
+    ];
+
+    tb.compare_updates(&expected_updates, &actual_updates, &mut state_clone)
+}
+
+#[test]
+fn skips_zero_shares() {
+    let mut tb = PlasmaTestBuilder::new();
+    let (big, big_account, _) = tb.add_account(false);
+    let (tiny, _tiny_account, _) = tb.add_account(false);
+
+    let mut state_clone = tb.state.clone();
+
+    // With a weight of 1 out of 1000, `tiny`'s floored share of a single token is 0,
+    // so it should not appear in the emitted updates at all.
+    let actual_updates = tb.state.distribute_fees(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(1u32),
+        }],
+        &[(big, 999), (tiny, 1)],
+    );
+
+    let expected_updates = [(
+        big,
+        AccountUpdate::UpdateBalance {
+            old_nonce: big_account.nonce,
+            new_nonce: big_account.nonce,
+            balance_update: (0, BigUint::from(0u32), BigUint::from(1u32)),
+        },
+    )];
+
+    tb.compare_updates(&expected_updates, &actual_updates, &mut state_clone)
+}
+
+#[test]
+#[should_panic]
+fn invalid_recipient() {
+    let mut tb = PlasmaTestBuilder::new();
+    tb.state.distribute_fees(
+        &[CollectedFee {
+            token: 0,
+            amount: BigUint::from(1u32),
+        }],
+        &[(145, 1)],
+    );
+}
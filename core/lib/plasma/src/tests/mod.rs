@@ -0,0 +1,82 @@
+use crate::state::PlasmaState;
+use models::account::{Account, AccountId, AccountUpdate};
+use num::BigUint;
+
+mod balances_summary;
+mod capitalization;
+mod collect_fee;
+mod distribute_fees;
+
+/// Test-only helper for building up a `PlasmaState` and asserting on the updates it emits.
+pub struct PlasmaTestBuilder {
+    pub state: PlasmaState,
+    next_account_id: AccountId,
+}
+
+impl PlasmaTestBuilder {
+    pub fn new() -> Self {
+        Self {
+            state: PlasmaState::empty(),
+            next_account_id: 0,
+        }
+    }
+
+    /// Adds a fresh, zero-balance account to the state and returns its id, a copy of the
+    /// account, and a `()` placeholder where the transaction-level test builders return a
+    /// signing key (the `locked` flag is likewise accepted for API parity but unused here).
+    pub fn add_account(&mut self, _locked: bool) -> (AccountId, Account, ()) {
+        let account_id = self.next_account_id;
+        self.next_account_id += 1;
+
+        let account = Account::default_with_id(account_id);
+        self.state.insert_account(account_id, account.clone());
+
+        (account_id, account, ())
+    }
+
+    pub fn set_balance<B: Into<BigUint>>(&mut self, account_id: AccountId, token: u16, amount: B) {
+        let mut account = self
+            .state
+            .get_account(account_id)
+            .expect("account does not exist");
+        account.set_balance(token, amount.into());
+        self.state.insert_account(account_id, account);
+    }
+
+    /// Asserts the actual updates produced by a state transition match what was expected,
+    /// then replays them onto `state_clone` and checks the resulting balances agree with
+    /// `self.state` so the two never silently diverge.
+    pub fn compare_updates(
+        &self,
+        expected_updates: &[(AccountId, AccountUpdate)],
+        actual_updates: &[(AccountId, AccountUpdate)],
+        state_clone: &mut PlasmaState,
+    ) {
+        assert_eq!(actual_updates, expected_updates);
+
+        for (account_id, update) in actual_updates {
+            let mut account = state_clone
+                .get_account(*account_id)
+                .expect("account does not exist in cloned state");
+
+            if let AccountUpdate::UpdateBalance {
+                balance_update: (token, _, new_amount),
+                new_nonce,
+                ..
+            } = update
+            {
+                account.set_balance(*token, new_amount.clone());
+                account.nonce = *new_nonce;
+            }
+
+            state_clone.insert_account(*account_id, account);
+        }
+
+        for (account_id, _) in actual_updates {
+            assert_eq!(
+                state_clone.get_account(*account_id),
+                self.state.get_account(*account_id),
+            );
+        }
+    }
+}